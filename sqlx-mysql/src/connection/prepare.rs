@@ -0,0 +1,127 @@
+use crate::connection::stmt_cache::{CachedStatement, CacheSize};
+use crate::protocol::statement::StmtClose;
+use crate::{MySqlConnection, Error};
+
+impl MySqlConnection {
+    /// Sets the capacity of this connection's prepared-statement cache.
+    ///
+    /// If the new size is smaller than the number of statements currently cached, the
+    /// least-recently-used entries are evicted (and closed server-side) immediately.
+    /// Setting this to [`CacheSize::Disabled`] closes every cached statement right away.
+    pub fn set_prepared_statement_cache_size(&mut self, size: CacheSize) {
+        self.inner.cache_statement.set_size(size);
+
+        if matches!(size, CacheSize::Disabled) {
+            let evicted = self.inner.cache_statement.clear();
+            self.close_statements(evicted);
+            return;
+        }
+
+        if let CacheSize::Bounded(capacity) = size {
+            while self.inner.cache_statement.len() > capacity {
+                // `set_size` already narrowed the capacity, so further inserts would
+                // evict on their own; here we proactively shrink down to the new limit.
+                if let Some(evicted) = self.inner.cache_statement.evict_one() {
+                    self.close_statements(vec![evicted]);
+                } else {
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Look up `sql` in the prepared-statement cache, preparing and (depending on the
+    /// configured [`CacheSize`]) caching it if it isn't already present.
+    ///
+    /// This is the entry point the query-execution path (`COM_STMT_EXECUTE`) must call
+    /// first: the returned [`CachedStatement`] carries the statement id to execute as
+    /// well as the param/result-column definitions needed to bind parameters and decode
+    /// the result set without a second round-trip to the server.
+    pub(crate) async fn get_or_prepare_statement(
+        &mut self,
+        sql: &str,
+    ) -> Result<CachedStatement, Error> {
+        // A MySQL connection only ever has one command in flight at a time, so by the
+        // time we're asked to prepare the *next* statement, any statement left over
+        // from a `CacheSize::Disabled` execute has definitely already run. Close it now
+        // rather than waiting indefinitely for something to drain it.
+        self.close_pending_statements();
+
+        if let Some(cached) = self.inner.cache_statement.get(sql) {
+            return Ok(cached.clone());
+        }
+
+        let resp = self.do_prepare(sql).await?;
+
+        let cached = CachedStatement {
+            statement_id: resp.ok.statement_id,
+            // The server only sends these once, during `COM_STMT_PREPARE`; there's no
+            // later point in the protocol to ask for them again, so the full
+            // definitions (not just `resp.ok.params`/`resp.ok.columns`'s counts) have
+            // to be cached alongside the statement id.
+            param_defs: resp.param_defs.unwrap_or_default(),
+            res_columns: resp.res_columns.unwrap_or_default(),
+        };
+
+        if self.inner.cache_statement.is_enabled() {
+            if let Some((_, evicted)) = self
+                .inner
+                .cache_statement
+                .insert(sql.to_owned(), cached.clone())
+            {
+                self.close_statements(vec![evicted]);
+            }
+        } else {
+            // `CacheSize::Disabled`: hand the id back to the caller for execution, but
+            // queue it to be closed with `COM_STMT_CLOSE` at the start of the next call
+            // to this function (see `close_pending_statements` above), rather than
+            // leaking the server-side handle.
+            self.inner.pending_close.push(cached.statement_id);
+        }
+
+        Ok(cached)
+    }
+
+    /// Closes every statement queued up by a `CacheSize::Disabled` execute. Called
+    /// before every subsequent prepare. [`MySqlConnection::reset`] does not call this;
+    /// it just drops the queue, since `COM_RESET_CONNECTION` already discards every
+    /// prepared statement server-side.
+    pub(crate) fn close_pending_statements(&mut self) {
+        if self.inner.pending_close.is_empty() {
+            return;
+        }
+
+        let pending = std::mem::take(&mut self.inner.pending_close);
+        let statements = pending
+            .into_iter()
+            .map(|statement_id| CachedStatement {
+                statement_id,
+                param_defs: Vec::new(),
+                res_columns: Vec::new(),
+            })
+            .collect();
+
+        self.close_statements(statements);
+    }
+
+    /// Closes every statement queued up for eviction or for `CacheSize::Disabled`
+    /// cleanup. Failures here are logged and otherwise ignored: the statement may
+    /// already be gone server-side (e.g. after a connection reset).
+    pub(crate) fn close_statements(&mut self, statements: Vec<CachedStatement>) {
+        for statement in statements {
+            if let Err(e) = self
+                .inner
+                .stream
+                .write_packet(&StmtClose {
+                    statement: statement.statement_id,
+                })
+            {
+                tracing::debug!(
+                    statement_id = statement.statement_id,
+                    error = %e,
+                    "failed to close evicted prepared statement; ignoring"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use futures_core::future::BoxFuture;
+
+use crate::pool::PoolConnectionMetadata;
+use crate::protocol::reset::ResetConnection;
+use crate::protocol::text::OkPacket;
+use crate::{Error, MySqlConnection};
+
+impl MySqlConnection {
+    /// Resets this connection's session state via `COM_RESET_CONNECTION`, without
+    /// paying the cost of a full reconnect handshake.
+    ///
+    /// This clears user variables, temporary tables, and the currently selected
+    /// database (reverting to the one set when the connection was opened), and rolls
+    /// back any open transaction. It is considerably cheaper than closing and
+    /// reopening the connection, which makes it a good fit for returning a connection
+    /// to a pool for reuse.
+    ///
+    /// The server drops all of our prepared statements as a side effect of this
+    /// command, so the client-side prepared-statement cache is cleared as well; the
+    /// next use of any previously-cached statement will transparently re-prepare it.
+    pub async fn reset(&mut self) -> Result<(), Error> {
+        self.inner.stream.send_packet(ResetConnection).await?;
+        self.inner.stream.recv_packet().await?.ok_into::<OkPacket>()?;
+
+        // The server has already forgotten every statement we had prepared; drop our
+        // local bookkeeping to match rather than attempting `COM_STMT_CLOSE` on ids
+        // that no longer exist.
+        self.inner.cache_statement.clear();
+        self.inner.pending_close.clear();
+
+        Ok(())
+    }
+}
+
+/// A ready-made [`PoolOptions::after_release`] hook that resets a connection via
+/// [`MySqlConnection::reset`] and keeps it alive, instead of the default behavior of
+/// closing it and opening a new one on the next `acquire()`.
+///
+/// Not applied automatically: `PoolOptions<MySql>` is a plain alias for
+/// `sqlx_core::pool::PoolOptions<MySql>`, so there's nowhere to hang a MySql-specific
+/// default on `PoolOptions::new()` itself. Opt in explicitly where it's safe to do so,
+/// re-exported at the crate root for this purpose:
+///
+/// ```ignore
+/// PoolOptions::new().after_release(sqlx_mysql::reset_on_release)
+/// ```
+///
+/// Only reuse connections this way within a single async runtime for their whole
+/// lifetime — Tokio's I/O streams do not tolerate being driven from a different runtime
+/// than the one they were opened on, so a pool whose connections might be acquired from
+/// more than one runtime (e.g. a process-wide singleton shared across `#[tokio::test]`
+/// functions, each with its own runtime) should keep closing connections on release
+/// instead. This is exactly why `testing::test_context` only applies it to the
+/// short-lived per-test pool and not to `MASTER_POOL`.
+///
+/// [`PoolOptions::after_release`]: crate::pool::PoolOptions::after_release
+pub fn reset_on_release(
+    conn: &mut MySqlConnection,
+    _meta: PoolConnectionMetadata,
+) -> BoxFuture<'_, Result<bool, Error>> {
+    Box::pin(async move { Ok(conn.reset().await.is_ok()) })
+}
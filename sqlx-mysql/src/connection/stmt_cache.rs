@@ -0,0 +1,206 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::protocol::ColumnDefPacket;
+
+/// Controls how many server-side prepared statements a [`MySqlConnection`] retains at
+/// once.
+///
+/// Set via [`MySqlConnectOptions::statement_cache_size`] or, for an already-established
+/// connection, [`MySqlConnection::set_prepared_statement_cache_size`].
+///
+/// [`MySqlConnection`]: crate::MySqlConnection
+/// [`MySqlConnectOptions::statement_cache_size`]: crate::MySqlConnectOptions::statement_cache_size
+/// [`MySqlConnection::set_prepared_statement_cache_size`]: crate::MySqlConnection::set_prepared_statement_cache_size
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheSize {
+    /// Never evict a cached prepared statement; the cache grows for the lifetime of the
+    /// connection.
+    Unbounded,
+
+    /// Do not cache prepared statements at all. Each statement is queued for
+    /// `COM_STMT_CLOSE` when it is prepared and closed when the connection next prepares
+    /// a statement; it is not closed as soon as its execution finishes.
+    Disabled,
+
+    /// Keep at most this many prepared statements, evicting the least-recently-used
+    /// entry (via `COM_STMT_CLOSE`) once the limit would otherwise be exceeded.
+    ///
+    /// `Bounded(0)` behaves like `Disabled`.
+    Bounded(usize),
+}
+
+impl Default for CacheSize {
+    fn default() -> Self {
+        // Matches the capacity used before this was made configurable.
+        CacheSize::Bounded(100)
+    }
+}
+
+/// The decoded `COM_STMT_PREPARE_OK` response for a cached statement, plus the param
+/// and result-column definitions the server only sends once, during `COM_STMT_PREPARE`.
+///
+/// Caching the full definitions (not just their counts) is what makes a cache hit
+/// actually usable: they're what `COM_STMT_EXECUTE`'s binary result set is decoded
+/// against, and there's no later point in the protocol to ask the server for them
+/// again.
+#[derive(Debug, Clone)]
+pub(crate) struct CachedStatement {
+    pub(crate) statement_id: u32,
+    pub(crate) param_defs: Vec<ColumnDefPacket>,
+    pub(crate) res_columns: Vec<ColumnDefPacket>,
+}
+
+/// An LRU cache of prepared statements, keyed by the SQL text that produced them.
+///
+/// Eviction order is tracked separately from the backing map so a cache hit can promote
+/// its entry to most-recently-used without touching every other entry.
+#[derive(Debug)]
+pub(crate) struct StatementCache {
+    size: CacheSize,
+    entries: HashMap<String, CachedStatement>,
+    // Least-recently-used at the front, most-recently-used at the back.
+    lru: VecDeque<String>,
+}
+
+impl StatementCache {
+    pub(crate) fn new(size: CacheSize) -> Self {
+        Self {
+            size,
+            entries: HashMap::new(),
+            lru: VecDeque::new(),
+        }
+    }
+
+    pub(crate) fn size(&self) -> CacheSize {
+        self.size
+    }
+
+    pub(crate) fn set_size(&mut self, size: CacheSize) {
+        self.size = size;
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        !matches!(self.size, CacheSize::Disabled | CacheSize::Bounded(0))
+    }
+
+    pub(crate) fn get(&mut self, sql: &str) -> Option<&CachedStatement> {
+        if self.entries.contains_key(sql) {
+            self.touch(sql);
+        }
+
+        self.entries.get(sql)
+    }
+
+    /// Insert a freshly prepared statement, returning the `(sql, statement)` evicted to
+    /// make room for it, if any.
+    pub(crate) fn insert(
+        &mut self,
+        sql: String,
+        statement: CachedStatement,
+    ) -> Option<(String, CachedStatement)> {
+        let evicted = match self.size {
+            CacheSize::Bounded(capacity) if !self.entries.contains_key(&sql) => {
+                if self.entries.len() >= capacity {
+                    self.evict_lru()
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        };
+
+        self.touch_or_insert(&sql);
+        self.entries.insert(sql, statement);
+
+        evicted
+    }
+
+    pub(crate) fn remove(&mut self, sql: &str) -> Option<CachedStatement> {
+        self.lru.retain(|cached| cached != sql);
+        self.entries.remove(sql)
+    }
+
+    /// Remove every entry, returning them so the caller can close them server-side.
+    pub(crate) fn clear(&mut self) -> Vec<CachedStatement> {
+        self.lru.clear();
+        self.entries.drain().map(|(_, stmt)| stmt).collect()
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Evict and return the single least-recently-used entry, if any.
+    pub(crate) fn evict_one(&mut self) -> Option<CachedStatement> {
+        self.evict_lru().map(|(_, stmt)| stmt)
+    }
+
+    fn touch(&mut self, sql: &str) {
+        if let Some(pos) = self.lru.iter().position(|cached| cached == sql) {
+            let sql = self.lru.remove(pos).expect("position was just found");
+            self.lru.push_back(sql);
+        }
+    }
+
+    fn touch_or_insert(&mut self, sql: &str) {
+        self.lru.retain(|cached| cached != sql);
+        self.lru.push_back(sql.to_owned());
+    }
+
+    fn evict_lru(&mut self) -> Option<(String, CachedStatement)> {
+        let sql = self.lru.pop_front()?;
+        let stmt = self.entries.remove(&sql)?;
+        Some((sql, stmt))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stmt(id: u32) -> CachedStatement {
+        CachedStatement {
+            statement_id: id,
+            param_defs: Vec::new(),
+            res_columns: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn bounded_cache_evicts_least_recently_used() {
+        let mut cache = StatementCache::new(CacheSize::Bounded(2));
+
+        assert!(cache.insert("a".into(), stmt(1)).is_none());
+        assert!(cache.insert("b".into(), stmt(2)).is_none());
+
+        // Touching "a" makes "b" the least-recently-used entry.
+        assert!(cache.get("a").is_some());
+
+        let evicted = cache.insert("c".into(), stmt(3));
+        assert_eq!(evicted.map(|(sql, _)| sql), Some("b".to_string()));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn unbounded_cache_never_evicts() {
+        let mut cache = StatementCache::new(CacheSize::Unbounded);
+
+        for i in 0..10 {
+            assert!(cache.insert(i.to_string(), stmt(i)).is_none());
+        }
+
+        assert_eq!(cache.len(), 10);
+    }
+
+    #[test]
+    fn bounded_zero_behaves_like_disabled() {
+        let cache = StatementCache::new(CacheSize::Bounded(0));
+        assert!(!cache.is_enabled());
+    }
+
+    #[test]
+    fn disabled_cache_is_not_enabled() {
+        let cache = StatementCache::new(CacheSize::Disabled);
+        assert!(!cache.is_enabled());
+    }
+}
@@ -0,0 +1,16 @@
+use crate::connection::stmt_cache::CacheSize;
+use crate::MySqlConnectOptions;
+
+impl MySqlConnectOptions {
+    /// Sets the capacity of the prepared-statement cache that new connections made
+    /// with these options will start with.
+    ///
+    /// To change the cache size of a connection that has already been established, use
+    /// [`MySqlConnection::set_prepared_statement_cache_size`] instead.
+    ///
+    /// [`MySqlConnection::set_prepared_statement_cache_size`]: crate::MySqlConnection::set_prepared_statement_cache_size
+    pub fn statement_cache_size(mut self, size: CacheSize) -> Self {
+        self.statement_cache_size = size;
+        self
+    }
+}
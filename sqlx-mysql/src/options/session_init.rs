@@ -0,0 +1,94 @@
+use crate::MySqlConnectOptions;
+
+impl MySqlConnectOptions {
+    /// Sets the character set to request via `SET NAMES` on every new physical
+    /// connection, e.g. `"utf8mb4"`.
+    ///
+    /// Combine with [`PoolOptions::after_connect`] (or an equivalent hook) to apply it
+    /// automatically instead of issuing the statement by hand after every `acquire()`.
+    ///
+    /// [`PoolOptions::after_connect`]: crate::pool::PoolOptions::after_connect
+    pub fn charset(mut self, charset: impl Into<String>) -> Self {
+        self.charset = Some(charset.into());
+        self
+    }
+
+    /// Sets the session time zone to request via `SET time_zone` on every new physical
+    /// connection, e.g. `"+00:00"` or `"America/New_York"`.
+    pub fn timezone(mut self, timezone: impl Into<String>) -> Self {
+        self.timezone = Some(timezone.into());
+        self
+    }
+
+    /// Sets the session `sql_mode` to request on every new physical connection, e.g.
+    /// `"STRICT_ALL_TABLES"`.
+    pub fn sql_mode(mut self, sql_mode: impl Into<String>) -> Self {
+        self.sql_mode = Some(sql_mode.into());
+        self
+    }
+
+    /// Builds the list of `SET` statements implied by [`charset`][Self::charset],
+    /// [`timezone`][Self::timezone] and [`sql_mode`][Self::sql_mode], if any were set.
+    ///
+    /// Returns an empty `Vec` if none of the three were configured, so callers can skip
+    /// the round-trip entirely for connections that don't need session initialization.
+    ///
+    /// Each statement is returned separately (rather than joined into one string) since
+    /// sqlx does not enable `CLIENT_MULTI_STATEMENTS`, so the server would reject more
+    /// than one statement in a single `COM_QUERY`. Callers must `execute()` each one in
+    /// turn.
+    pub(crate) fn session_init_sql(&self) -> Vec<String> {
+        let mut statements = Vec::new();
+
+        if let Some(charset) = &self.charset {
+            statements.push(format!("SET NAMES '{}'", escape_string_literal(charset)));
+        }
+
+        if let Some(timezone) = &self.timezone {
+            statements.push(format!(
+                "SET time_zone = '{}'",
+                escape_string_literal(timezone)
+            ));
+        }
+
+        if let Some(sql_mode) = &self.sql_mode {
+            statements.push(format!(
+                "SET SESSION sql_mode = '{}'",
+                escape_string_literal(sql_mode)
+            ));
+        }
+
+        statements
+    }
+}
+
+/// Escapes `value` for safe interpolation into a single-quoted MySQL string literal.
+///
+/// Backslash is MySQL's escape character by default (`NO_BACKSLASH_ESCAPES` isn't set
+/// by sqlx), so it must be escaped along with the quote itself, and in that order, or a
+/// value ending in a backslash would swallow the closing quote.
+fn escape_string_literal(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('\'', "\\'")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::escape_string_literal;
+
+    #[test]
+    fn escapes_single_quotes() {
+        assert_eq!(escape_string_literal("o'clock"), "o\\'clock");
+    }
+
+    #[test]
+    fn escapes_backslashes_before_quotes() {
+        // A trailing backslash must be escaped first, or it would swallow the
+        // statement's closing quote.
+        assert_eq!(escape_string_literal(r"utf8\"), r"utf8\\");
+    }
+
+    #[test]
+    fn leaves_plain_values_untouched() {
+        assert_eq!(escape_string_literal("utf8mb4"), "utf8mb4");
+    }
+}
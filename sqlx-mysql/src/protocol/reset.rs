@@ -0,0 +1,23 @@
+use crate::protocol::Capabilities;
+use sqlx_core::io::ProtocolEncode;
+use sqlx_core::Error;
+
+const COM_RESET_CONNECTION: u8 = 0x1F;
+
+/// `COM_RESET_CONNECTION` — resets the session state of the current connection without
+/// closing and re-establishing it.
+///
+/// This clears user variables, rolls back any open transaction, drops temporary tables,
+/// resets the session to the default character set and collation, and re-selects the
+/// database that was active when the connection handshake completed. It also drops
+/// every prepared statement the server is holding on our behalf, so the client-side
+/// statement cache must be cleared alongside this.
+pub(crate) struct ResetConnection;
+
+impl ProtocolEncode<'_, Capabilities> for ResetConnection {
+    fn encode_with(&self, buf: &mut Vec<u8>, _: Capabilities) -> Result<(), Error> {
+        buf.push(COM_RESET_CONNECTION);
+
+        Ok(())
+    }
+}
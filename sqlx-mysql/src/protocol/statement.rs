@@ -0,0 +1,24 @@
+use crate::io::MySqlWriteExt;
+use crate::protocol::Capabilities;
+use sqlx_core::io::ProtocolEncode;
+use sqlx_core::Error;
+
+const COM_STMT_CLOSE: u8 = 0x19;
+
+/// `COM_STMT_CLOSE` — deallocates a prepared statement on the server.
+///
+/// The server does not send a response to this command, even if the statement id is
+/// unknown (e.g. because it already expired), so the only way closing a statement can
+/// fail is in sending the command.
+pub(crate) struct StmtClose {
+    pub(crate) statement: u32,
+}
+
+impl ProtocolEncode<'_, Capabilities> for StmtClose {
+    fn encode_with(&self, buf: &mut Vec<u8>, _: Capabilities) -> Result<(), Error> {
+        buf.push(COM_STMT_CLOSE);
+        buf.extend_from_slice(&self.statement.to_le_bytes());
+
+        Ok(())
+    }
+}
@@ -4,6 +4,7 @@ use sqlx_core::connection::Connection;
 use sqlx_core::query_scalar::query_scalar;
 use std::ops::Deref;
 use std::str::FromStr;
+use std::sync::Mutex;
 use std::time::Duration;
 
 use crate::error::Error;
@@ -13,10 +14,18 @@ use crate::query::query;
 use crate::{MySql, MySqlConnectOptions, MySqlConnection};
 pub(crate) use sqlx_core::testing::*;
 
+mod snapshot;
+
 // Using a blocking `OnceCell` here because the critical sections are short.
 static MASTER_POOL: OnceCell<Pool<MySql>> = OnceCell::new();
 // Automatically delete any databases created before the start of the test binary.
 
+// The first successful `MySql::snapshot()` call is normally made against a
+// freshly-migrated template database; caching its tables here lets every later
+// `test_context()` restore them into the new per-test database directly, instead of
+// requiring the test harness to re-run the full migration history for every test.
+static TEMPLATE_SNAPSHOT: OnceCell<Mutex<Option<Vec<snapshot::CapturedTable>>>> = OnceCell::new();
+
 impl TestSupport for MySql {
     fn test_context(args: &TestArgs) -> BoxFuture<'_, Result<TestContext<Self>, Error>> {
         Box::pin(async move { test_context(args).await })
@@ -110,25 +119,55 @@ impl TestSupport for MySql {
     }
 
     fn snapshot(
-        _conn: &mut Self::Connection,
+        conn: &mut Self::Connection,
     ) -> BoxFuture<'_, Result<FixtureSnapshot<Self>, Error>> {
-        // TODO: I want to get the testing feature out the door so this will have to wait,
-        // but I'm keeping the code around for now because I plan to come back to it.
-        todo!()
+        Box::pin(async move {
+            let tables = snapshot::capture(conn).await?;
+
+            *TEMPLATE_SNAPSHOT
+                .get_or_init(|| Mutex::new(None))
+                .lock()
+                .unwrap() = Some(tables.clone());
+
+            Ok(snapshot::to_fixture_snapshot(&tables))
+        })
     }
 }
 
 async fn test_context(args: &TestArgs) -> Result<TestContext<MySql>, Error> {
     let url = dotenvy::var("DATABASE_URL").expect("DATABASE_URL must be set");
 
-    let master_opts = MySqlConnectOptions::from_str(&url).expect("failed to parse DATABASE_URL");
+    let master_opts = MySqlConnectOptions::from_str(&url)
+        .expect("failed to parse DATABASE_URL")
+        // Keep test databases consistent regardless of the server's own defaults.
+        .charset("utf8mb4")
+        .sql_mode("STRICT_ALL_TABLES");
+
+    let session_init_sql = master_opts.session_init_sql();
 
     let pool = PoolOptions::new()
         // MySql's normal connection limit is 150 plus 1 superuser connection
         // We don't want to use the whole cap and there may be fuzziness here due to
         // concurrently running tests anyway.
         .max_connections(20)
-        // Immediately close master connections. Tokio's I/O streams don't like hopping runtimes.
+        // Run session-initialization `SET` statements once per physical connection,
+        // rather than requiring every caller to issue them after every `acquire()`.
+        .after_connect(move |conn, _meta| {
+            let session_init_sql = session_init_sql.clone();
+            Box::pin(async move {
+                // Issued one at a time: sqlx doesn't enable `CLIENT_MULTI_STATEMENTS`,
+                // so the server would reject more than one statement per `execute()`.
+                for sql in &session_init_sql {
+                    conn.execute(sql.as_str()).await?;
+                }
+                Ok(())
+            })
+        })
+        // Immediately close master connections. Tokio's I/O streams don't like hopping
+        // runtimes, and `MASTER_POOL` is a process-wide singleton that can be acquired
+        // from whatever runtime each `#[tokio::test]` spins up, so we can't safely keep
+        // one of its connections alive past the test that opened it. The short-lived
+        // per-test pool below doesn't have this problem and resets instead.
         .after_release(|_conn, _| Box::pin(async move { Ok(false) }))
         .connect_lazy_with(master_opts);
 
@@ -186,6 +225,17 @@ async fn test_context(args: &TestArgs) -> Result<TestContext<MySql>, Error> {
     conn.execute(format!("CREATE DATABASE {db_name};").as_str())
         .await?;
 
+    // If a template snapshot has already been captured via `MySql::snapshot()`,
+    // restore it into the new database now instead of leaving it empty; the caller
+    // skips re-running migrations against it.
+    if let Some(tables) = TEMPLATE_SNAPSHOT
+        .get()
+        .and_then(|cell| cell.lock().unwrap().clone())
+    {
+        conn.execute(format!("USE {db_name};").as_str()).await?;
+        snapshot::apply(&mut *conn, &tables).await?;
+    }
+
     Ok(TestContext {
         pool_opts: PoolOptions::new()
             // Don't allow a single test to take all the connections.
@@ -194,6 +244,10 @@ async fn test_context(args: &TestArgs) -> Result<TestContext<MySql>, Error> {
             .max_connections(5)
             // Close connections ASAP if left in the idle queue.
             .idle_timeout(Some(Duration::from_secs(1)))
+            // Unlike `MASTER_POOL`, this pool lives and dies within a single test (and
+            // therefore a single runtime), so resetting and reusing a connection on
+            // release is safe and saves a reconnect between test bodies.
+            .after_release(|conn, meta| crate::connection::reset::reset_on_release(conn, meta))
             .parent(master_pool.clone()),
         connect_opts: master_pool
             .connect_options()
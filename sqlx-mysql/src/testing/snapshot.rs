@@ -0,0 +1,220 @@
+use std::collections::{HashMap, HashSet};
+
+use sqlx_core::query_as::query_as;
+use sqlx_core::query_scalar::query_scalar;
+use sqlx_core::row::Row;
+
+use crate::error::Error;
+use crate::executor::Executor;
+use crate::query::query;
+use crate::testing::FixtureSnapshot;
+use crate::{MySql, MySqlConnection};
+
+/// The schema and row data captured for a single table, enough to recreate it
+/// elsewhere via [`apply`].
+#[derive(Debug, Clone)]
+pub(super) struct CapturedTable {
+    name: String,
+    create_table_sql: String,
+    columns: Vec<String>,
+    rows: Vec<Vec<Option<String>>>,
+}
+
+/// Captures the schema and data of every user table in the database `conn` is
+/// currently connected to, in an order safe to replay (each table after the tables
+/// it has foreign keys to).
+///
+/// This is meant to run once against a freshly-migrated template database; the result
+/// is then cheap to re-materialize (via [`apply`]) into each per-test database instead
+/// of re-running the full migration history for every test.
+pub(super) async fn capture(conn: &mut MySqlConnection) -> Result<Vec<CapturedTable>, Error> {
+    let mut tables = Vec::new();
+
+    for table in tables_in_dependency_order(conn).await? {
+        // `SHOW CREATE TABLE` returns two columns, `(Table, Create Table)`; we only
+        // want the DDL, but `query_scalar` only decodes a single column, so use
+        // `query_as` to pull the whole row and discard the name.
+        let (_name, create_table_sql): (String, String) =
+            query_as(&format!("SHOW CREATE TABLE `{table}`"))
+                .fetch_one(&mut *conn)
+                .await?;
+
+        let rows = query(&format!("SELECT * FROM `{table}`"))
+            .fetch_all(&mut *conn)
+            .await?;
+
+        let columns: Vec<String> = rows
+            .first()
+            .map(|row| row.columns().iter().map(|c| c.name().to_owned()).collect())
+            .unwrap_or_default();
+
+        let mut values = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let mut captured_row = Vec::with_capacity(row.len());
+            for i in 0..row.len() {
+                // `try_get_unchecked` skips the usual type-compatibility check, which
+                // matters here: every column comes back over the text protocol as a
+                // textual representation regardless of its declared type (`INT`,
+                // `TIMESTAMP`, `DECIMAL`, ...), but `try_get` would reject decoding any
+                // of those as `String` as a type mismatch. A real SQL `NULL` still
+                // decodes to `Ok(None)`; only a genuine decode failure (e.g. non-UTF-8
+                // `BLOB` content) should surface as an error here, not silently become
+                // a `NULL` in the snapshot.
+                captured_row.push(row.try_get_unchecked::<Option<String>, _>(i)?);
+            }
+            values.push(captured_row);
+        }
+
+        tables.push(CapturedTable {
+            name: table,
+            create_table_sql,
+            columns,
+            rows: values,
+        });
+    }
+
+    Ok(tables)
+}
+
+/// Builds the generic [`FixtureSnapshot`] that [`TestSupport::snapshot`] hands back to
+/// the caller, from the tables captured by [`capture`].
+///
+/// [`TestSupport::snapshot`]: sqlx_core::testing::TestSupport::snapshot
+pub(super) fn to_fixture_snapshot(tables: &[CapturedTable]) -> FixtureSnapshot<MySql> {
+    let mut snapshot = FixtureSnapshot::new();
+
+    for table in tables {
+        snapshot.insert_table(
+            table.name.clone(),
+            table.create_table_sql.clone(),
+            table.columns.clone(),
+            table.rows.clone(),
+        );
+    }
+
+    snapshot
+}
+
+/// The most rows bulk-inserted by a single `INSERT` statement in [`apply`].
+///
+/// Keeps the generated statement (and its bind parameter count) bounded for very large
+/// tables, rather than trying to insert every captured row in one go.
+const MAX_ROWS_PER_INSERT: usize = 500;
+
+/// Recreates every table in `tables` and bulk-inserts its rows, in the same
+/// dependency-safe order they were captured in.
+pub(super) async fn apply(conn: &mut MySqlConnection, tables: &[CapturedTable]) -> Result<(), Error> {
+    conn.execute("SET FOREIGN_KEY_CHECKS = 0").await?;
+
+    // Restore `FOREIGN_KEY_CHECKS` even if a `CREATE TABLE`/`INSERT` below fails,
+    // rather than leaving it disabled on `conn` for whatever the caller does with it
+    // next.
+    let result = apply_tables(conn, tables).await;
+
+    conn.execute("SET FOREIGN_KEY_CHECKS = 1").await?;
+
+    result
+}
+
+async fn apply_tables(conn: &mut MySqlConnection, tables: &[CapturedTable]) -> Result<(), Error> {
+    for table in tables {
+        conn.execute(table.create_table_sql.as_str()).await?;
+
+        if table.rows.is_empty() {
+            continue;
+        }
+
+        let columns = table.columns.join(", ");
+
+        let row_placeholders = format!(
+            "({})",
+            std::iter::repeat("?")
+                .take(table.columns.len())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+
+        for chunk in table.rows.chunks(MAX_ROWS_PER_INSERT) {
+            let values = std::iter::repeat(row_placeholders.as_str())
+                .take(chunk.len())
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            let insert_sql =
+                format!("INSERT INTO `{}` ({columns}) VALUES {values}", table.name);
+
+            let mut insert = query(&insert_sql);
+
+            for row in chunk {
+                for value in row {
+                    insert = insert.bind(value.clone());
+                }
+            }
+
+            insert.execute(&mut *conn).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns every base table in the current database, ordered so that a table is never
+/// listed before a table it has a foreign key to (a simple Kahn's-algorithm topological
+/// sort over `information_schema.key_column_usage`).
+async fn tables_in_dependency_order(conn: &mut MySqlConnection) -> Result<Vec<String>, Error> {
+    let tables: Vec<String> = query_scalar(
+        "SELECT table_name FROM information_schema.tables \
+         WHERE table_schema = DATABASE() AND table_type = 'BASE TABLE'",
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let edges: Vec<(String, String)> = query_as(
+        "SELECT table_name, referenced_table_name FROM information_schema.key_column_usage \
+         WHERE table_schema = DATABASE() AND referenced_table_name IS NOT NULL",
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut depends_on: HashMap<&str, HashSet<&str>> = HashMap::new();
+    for table in &tables {
+        depends_on.entry(table).or_default();
+    }
+    for (table, referenced) in &edges {
+        if table != referenced {
+            depends_on.entry(table).or_default().insert(referenced);
+        }
+    }
+
+    let mut ordered = Vec::with_capacity(tables.len());
+    let mut remaining = depends_on;
+
+    while !remaining.is_empty() {
+        let ready: Vec<String> = remaining
+            .iter()
+            .filter(|(_, deps)| deps.iter().all(|dep| !remaining.contains_key(dep)))
+            .map(|(table, _)| table.to_string())
+            .collect();
+
+        if ready.is_empty() {
+            // A dependency cycle (e.g. mutually-referencing tables); fall back to
+            // whatever order `information_schema` gave us for the rest rather than
+            // looping forever.
+            ordered.extend(
+                tables
+                    .iter()
+                    .filter(|t| remaining.contains_key(t.as_str()))
+                    .cloned(),
+            );
+            break;
+        }
+
+        for table in &ready {
+            remaining.remove(table.as_str());
+        }
+
+        ordered.extend(ready);
+    }
+
+    Ok(ordered)
+}